@@ -0,0 +1,192 @@
+//! End-to-end harness that drives the real task-creation path against an Anvil
+//! node running in a throwaway Docker container.
+//!
+//! The harness starts `anvil` inside the Foundry image on a random host port,
+//! deploys the Trappist service-manager contracts into it with `forge script`,
+//! then calls [`super::create_new_task`] and asserts the on-chain task counter
+//! advanced. The container is torn down when the [`AnvilContainer`] is dropped,
+//! so no developer has to remember to launch Anvil and Foundry by hand.
+
+use super::{create_new_task, TaskConfig};
+use crate::keystore::KeySource;
+use crate::task_store::{build_store, TaskStore};
+use alloy::primitives::Address;
+use eigen_utils::get_signer;
+use eyre::{eyre, Result};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use trappist_utils::{trappistservicemanager::trappistServiceManager, trappistData};
+
+/// Foundry image providing both `anvil` and `forge`.
+const FOUNDRY_IMAGE: &str = "ghcr.io/foundry-rs/foundry:latest";
+/// Anvil's first deterministic dev account private key (10k ETH prefunded).
+const PREFUNDED_KEY: &str =
+    "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Mount point for the repository inside the container.
+const CONTAINER_REPO: &str = "/work";
+
+/// A running Anvil container. Killed on drop.
+struct AnvilContainer {
+    id: String,
+    rpc_url: String,
+    keys: Vec<String>,
+    /// Host path of the repository mounted into the container.
+    repo_root: PathBuf,
+}
+
+impl AnvilContainer {
+    /// Launch `anvil` on a random host port and wait for it to accept RPC.
+    ///
+    /// The repository is bind-mounted into the container so `forge` can find
+    /// the deploy script and write the deployment descriptor back to a host
+    /// path the test can read.
+    fn start() -> Result<Self> {
+        let repo_root = repo_root()?;
+        let mount = format!("{}:{CONTAINER_REPO}", repo_root.display());
+        let id = docker(&[
+            "run", "-d", "--rm", "-v", &mount, "-w", CONTAINER_REPO, "-p", "0:8545",
+            FOUNDRY_IMAGE, "anvil", "--host", "0.0.0.0",
+        ])?
+        .trim()
+        .to_string();
+
+        // Resolve the random host port Docker bound to 8545.
+        let mapping = docker(&["port", &id, "8545/tcp"])?;
+        let port = mapping
+            .trim()
+            .rsplit(':')
+            .next()
+            .ok_or_else(|| eyre!("could not parse docker port mapping: {mapping}"))?;
+        let rpc_url = format!("http://127.0.0.1:{port}");
+
+        let container = Self {
+            id,
+            rpc_url,
+            keys: vec![PREFUNDED_KEY.to_string()],
+            repo_root,
+        };
+        container.wait_ready()?;
+        Ok(container)
+    }
+
+    /// Block until the node answers an RPC request or a timeout elapses.
+    fn wait_ready(&self) -> Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while Instant::now() < deadline {
+            if docker(&[
+                "exec", &self.id, "cast", "block-number", "--rpc-url",
+                "http://127.0.0.1:8545",
+            ])
+            .is_ok()
+            {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        Err(eyre!("anvil did not become ready in time"))
+    }
+
+    /// Deploy the Trappist service-manager contracts and return the host path
+    /// of the written deployment descriptor.
+    fn deploy_contracts(&self) -> Result<String> {
+        // Runs the repo's deploy script (found via the bind mount) against the
+        // container's RPC endpoint, funded by the first prefunded key. The
+        // script writes `DEPLOYMENT_PATH` inside the mounted tree, so the file
+        // lands on the host.
+        docker(&[
+            "exec", "-w", &format!("{CONTAINER_REPO}/contracts"), &self.id,
+            "forge", "script", "script/TrappistDeployer.s.sol",
+            "--rpc-url", "http://127.0.0.1:8545", "--private-key", &self.keys[0],
+            "--broadcast",
+        ])?;
+
+        let host_path = self.repo_root.join(super::DEPLOYMENT_PATH);
+        if !host_path.exists() {
+            return Err(eyre!(
+                "deploy script did not write descriptor at {}",
+                host_path.display()
+            ));
+        }
+        Ok(host_path.to_string_lossy().into_owned())
+    }
+}
+
+/// Walk up from the current directory to the repository root (the ancestor
+/// containing the `contracts` directory), so the bind mount and descriptor
+/// path resolve regardless of the test's working directory.
+fn repo_root() -> Result<PathBuf> {
+    let start = std::env::current_dir()?;
+    let mut dir: &Path = &start;
+    loop {
+        if dir.join("contracts").is_dir() {
+            return Ok(dir.to_path_buf());
+        }
+        dir = dir
+            .parent()
+            .ok_or_else(|| eyre!("could not locate repo root (no 'contracts' dir) from {}", start.display()))?;
+    }
+}
+
+impl Drop for AnvilContainer {
+    fn drop(&mut self) {
+        let _ = docker(&["kill", &self.id]);
+    }
+}
+
+/// Run a `docker` subcommand, returning stdout on success.
+fn docker(args: &[&str]) -> Result<String> {
+    let output = Command::new("docker").args(args).output()?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "docker {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Read the current `latestTaskNum` counter from the deployed contract.
+async fn latest_task_num(config: &TaskConfig) -> Result<u32> {
+    let data = std::fs::read_to_string(&config.deployment_path)?;
+    let parsed: trappistData = serde_json::from_str(&data)?;
+    let address: Address = parsed.addresses.trappist_service_manager.parse()?;
+    let pr = get_signer(PREFUNDED_KEY, &config.rpc_url);
+    let contract = trappistServiceManager::new(address, pr);
+    Ok(contract.latestTaskNum().call().await?._0)
+}
+
+#[tokio::test]
+#[ignore = "requires Docker; run explicitly in CI"]
+async fn create_new_task_increments_counter() -> Result<()> {
+    let anvil = AnvilContainer::start()?;
+    let deployment_path = anvil.deploy_contracts()?;
+
+    let config = TaskConfig {
+        rpc_url: anvil.rpc_url.clone(),
+        deployment_path,
+        key_source: KeySource::Raw {
+            private_key: anvil.keys[0].clone(),
+        },
+    };
+
+    let before = latest_task_num(&config).await?;
+
+    let store: Arc<dyn TaskStore> = build_store(None).await?;
+    create_new_task("IntegrationTestTask", &store, &config).await?;
+
+    let after = latest_task_num(&config).await?;
+    assert_eq!(after, before + 1, "task counter should advance by one");
+
+    // The store should now hold the recorded task.
+    let recent = store.recent(1).await?;
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].name, "IntegrationTestTask");
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+#![allow(missing_docs)]
+use aes::cipher::{KeyIvInit, StreamCipher};
+use alloy::{primitives::keccak256, signers::local::PrivateKeySigner};
+use eyre::{bail, eyre, Result};
+use serde::Deserialize;
+use std::{path::Path, str::FromStr};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Where the operator signer's private key comes from.
+pub enum KeySource {
+    /// Read the raw hex key from the `PRIVATE_KEY` environment variable.
+    EnvVar,
+    /// Decrypt an EIP-2335 / geth-style JSON keystore. `passphrase_env` names
+    /// the environment variable holding the decryption passphrase.
+    Keystore {
+        path: String,
+        passphrase_env: String,
+    },
+    /// A raw hex private key supplied directly, e.g. a prefunded key injected
+    /// by the integration-test harness.
+    Raw { private_key: String },
+}
+
+impl KeySource {
+    /// Resolve the source into a ready-to-use [`PrivateKeySigner`].
+    pub fn load(&self) -> Result<PrivateKeySigner> {
+        match self {
+            KeySource::EnvVar => {
+                let key = std::env::var("PRIVATE_KEY")
+                    .map_err(|_| eyre!("PRIVATE_KEY not set"))?;
+                Ok(PrivateKeySigner::from_str(&key)?)
+            }
+            KeySource::Keystore {
+                path,
+                passphrase_env,
+            } => {
+                let passphrase = std::env::var(passphrase_env)
+                    .map_err(|_| eyre!("{passphrase_env} not set"))?;
+                let key = decrypt_keystore(path, passphrase.as_bytes())?;
+                Ok(PrivateKeySigner::from_slice(&key)?)
+            }
+            KeySource::Raw { private_key } => Ok(PrivateKeySigner::from_str(private_key)?),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Keystore {
+    crypto: Crypto,
+}
+
+#[derive(Deserialize)]
+struct Crypto {
+    cipher: String,
+    ciphertext: String,
+    cipherparams: CipherParams,
+    kdf: String,
+    kdfparams: serde_json::Value,
+    mac: String,
+}
+
+#[derive(Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+/// Decrypt a Web3 Secret Storage keystore file into the raw 32-byte private key.
+///
+/// Follows the standard path: derive the symmetric key from `passphrase` with
+/// the declared KDF, verify the keccak MAC over the derived-key tail and
+/// ciphertext, then decrypt with AES-128-CTR. A MAC mismatch (wrong passphrase)
+/// is surfaced as an error rather than yielding a garbage key.
+pub fn decrypt_keystore(path: impl AsRef<Path>, passphrase: &[u8]) -> Result<Vec<u8>> {
+    let contents = std::fs::read_to_string(path)?;
+    decrypt_keystore_json(&contents, passphrase)
+}
+
+/// Decrypt a keystore already read into `json`. Split out from
+/// [`decrypt_keystore`] so the crypto path can be exercised without touching the
+/// filesystem.
+pub fn decrypt_keystore_json(json: &str, passphrase: &[u8]) -> Result<Vec<u8>> {
+    let store: Keystore = serde_json::from_str(json)?;
+    let crypto = store.crypto;
+
+    if crypto.cipher != "aes-128-ctr" {
+        bail!("unsupported cipher: {}", crypto.cipher);
+    }
+
+    let ciphertext = hex::decode(&crypto.ciphertext)?;
+    let derived = derive_key(&crypto.kdf, &crypto.kdfparams, passphrase)?;
+
+    // MAC = keccak256(derived_key[16..32] || ciphertext).
+    let mut mac_input = derived[16..32].to_vec();
+    mac_input.extend_from_slice(&ciphertext);
+    let mac = keccak256(&mac_input);
+    let expected = hex::decode(&crypto.mac)?;
+    if mac.as_slice() != expected.as_slice() {
+        bail!("keystore MAC mismatch: wrong passphrase");
+    }
+
+    let iv = hex::decode(&crypto.cipherparams.iv)?;
+    let mut key = ciphertext;
+    let mut cipher = Aes128Ctr::new_from_slices(&derived[..16], &iv)
+        .map_err(|e| eyre!("invalid AES key/iv length: {e}"))?;
+    cipher.apply_keystream(&mut key);
+
+    Ok(key)
+}
+
+/// Derive the 32-byte symmetric key using the keystore's declared KDF.
+fn derive_key(kdf: &str, params: &serde_json::Value, passphrase: &[u8]) -> Result<[u8; 32]> {
+    let dklen = params["dklen"].as_u64().unwrap_or(32) as usize;
+    if dklen < 32 {
+        bail!("keystore dklen {dklen} too small: need at least 32 bytes for the key");
+    }
+    let salt = hex::decode(params["salt"].as_str().ok_or_else(|| eyre!("missing salt"))?)?;
+    let mut out = vec![0u8; dklen];
+
+    match kdf {
+        "scrypt" => {
+            let n = params["n"].as_u64().ok_or_else(|| eyre!("missing scrypt n"))?;
+            let r = params["r"].as_u64().ok_or_else(|| eyre!("missing scrypt r"))? as u32;
+            let p = params["p"].as_u64().ok_or_else(|| eyre!("missing scrypt p"))? as u32;
+            let log_n = (n as f64).log2() as u8;
+            let sparams = scrypt::Params::new(log_n, r, p, dklen)
+                .map_err(|e| eyre!("invalid scrypt params: {e}"))?;
+            scrypt::scrypt(passphrase, &salt, &sparams, &mut out)
+                .map_err(|e| eyre!("scrypt failed: {e}"))?;
+        }
+        "pbkdf2" => {
+            let c = params["c"].as_u64().ok_or_else(|| eyre!("missing pbkdf2 c"))? as u32;
+            let prf = params["prf"].as_str().unwrap_or("hmac-sha256");
+            if prf != "hmac-sha256" {
+                bail!("unsupported pbkdf2 prf: {prf}");
+            }
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase, &salt, c, &mut out);
+        }
+        other => bail!("unsupported kdf: {other}"),
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&out[..32]);
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Canonical pbkdf2 vector from the Web3 Secret Storage / EIP-2335 spec.
+    // Passphrase "testpassword" decrypts to the documented private key.
+    const VECTOR: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    const EXPECTED_KEY: &str =
+        "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn decrypts_canonical_vector() {
+        let key = decrypt_keystore_json(VECTOR, b"testpassword").unwrap();
+        assert_eq!(hex::encode(key), EXPECTED_KEY);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_mac_mismatch() {
+        let err = decrypt_keystore_json(VECTOR, b"wrongpassword").unwrap_err();
+        assert!(
+            err.to_string().contains("MAC mismatch"),
+            "expected MAC mismatch, got: {err}"
+        );
+    }
+
+    #[test]
+    fn rejects_short_dklen() {
+        let params = serde_json::json!({
+            "c": 1,
+            "dklen": 16,
+            "prf": "hmac-sha256",
+            "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+        });
+        let err = derive_key("pbkdf2", &params, b"pw").unwrap_err();
+        assert!(err.to_string().contains("dklen"), "got: {err}");
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use alloy::primitives::Address;
 use dotenv::dotenv;
 use eigen_logging::{get_logger, init_logger, log_level::LogLevel};
 use eigen_utils::get_signer;
@@ -7,14 +7,59 @@ use eyre::Result;
 use trappist_utils::{trappistservicemanager::trappistServiceManager, trappistData};
 use once_cell::sync::Lazy;
 use rand::Rng;
-use std::{env, str::FromStr};
+use std::env;
 use tokio::time::{self, Duration};
 
+mod job_queue;
+mod keystore;
+mod task_store;
+mod task_watcher;
+
+#[cfg(test)]
+mod integration_tests;
+
+use job_queue::{JobQueue, SubmitTaskJob};
+use keystore::KeySource;
+use std::sync::Arc;
+use task_store::{build_store, PgConfig, TaskRecord, TaskStore};
+use task_watcher::TaskWatcher;
+
 pub const ANVIL_RPC_URL: &str = "http://localhost:8545";
 
-#[allow(unused)]
-static KEY: Lazy<String> =
-    Lazy::new(|| env::var("PRIVATE_KEY").expect("failed to retrieve private key"));
+/// Default location of the Trappist deployment descriptor on an Anvil chain.
+pub const DEPLOYMENT_PATH: &str = "contracts/deployments/trappist/31337.json";
+
+/// Runtime inputs for task creation. Kept as a value rather than module
+/// constants so callers — including the integration-test harness — can inject a
+/// container's RPC URL, deployment path, and funded key.
+pub struct TaskConfig {
+    /// JSON-RPC endpoint of the target chain.
+    pub rpc_url: String,
+    /// Path to the deployment descriptor holding the contract addresses.
+    pub deployment_path: String,
+    /// Where to load the submitting signer from.
+    pub key_source: KeySource,
+}
+
+impl Default for TaskConfig {
+    /// The production defaults: local Anvil, the checked-in deployment path, and
+    /// a key selected from the environment (`KEYSTORE_PATH` for a keystore,
+    /// otherwise `PRIVATE_KEY`).
+    fn default() -> Self {
+        let key_source = match env::var("KEYSTORE_PATH") {
+            Ok(path) => KeySource::Keystore {
+                path,
+                passphrase_env: "KEYSTORE_PASSPHRASE".to_string(),
+            },
+            Err(_) => KeySource::EnvVar,
+        };
+        Self {
+            rpc_url: ANVIL_RPC_URL.to_string(),
+            deployment_path: DEPLOYMENT_PATH.to_string(),
+            key_source,
+        }
+    }
+}
 
 #[allow(unused)]
 /// Generate random task names from the given adjectives and nouns
@@ -32,14 +77,20 @@ fn generate_random_name() -> String {
 }
 
 #[allow(unused)]
-/// Calls CreateNewTask function of the Trappist service manager contract
-async fn create_new_task(task_name: &str) -> Result<()> {
-    let data = std::fs::read_to_string("contracts/deployments/trappist/31337.json")?;
+/// Calls CreateNewTask function of the Trappist service manager contract and
+/// records the submitted task in the store for durable history.
+async fn create_new_task(
+    task_name: &str,
+    store: &Arc<dyn TaskStore>,
+    config: &TaskConfig,
+) -> Result<()> {
+    let data = std::fs::read_to_string(&config.deployment_path)?;
     let parsed: trappistData = serde_json::from_str(&data)?;
     let trappist_contract_address: Address =
         parsed.addresses.trappist_service_manager.parse()?;
-    let pr = get_signer(&KEY.clone(), ANVIL_RPC_URL);
-    let signer = PrivateKeySigner::from_str(&KEY.clone())?;
+    let signer = config.key_source.load()?;
+    let key_hex = hex::encode(signer.to_bytes());
+    let pr = get_signer(&key_hex, &config.rpc_url);
     let trappist_contract = trappistServiceManager::new(trappist_contract_address, pr);
 
     let tx = trappist_contract
@@ -54,14 +105,95 @@ async fn create_new_task(task_name: &str) -> Result<()> {
         tx.transaction_hash
     );
 
+    // Recover the assigned task index from the emitted `NewTaskCreated` log.
+    use alloy::sol_types::SolEvent;
+    use trappist_utils::trappistservicemanager::trappistServiceManager::NewTaskCreated;
+    let task_index = tx
+        .inner
+        .logs()
+        .iter()
+        .find_map(|log| log.log_decode::<NewTaskCreated>().ok())
+        .map(|ev| ev.inner.taskIndex)
+        .ok_or_else(|| eyre::eyre!("no NewTaskCreated log in receipt for task '{task_name}'"))?;
+
+    store
+        .record_created(TaskRecord {
+            name: task_name.to_string(),
+            task_index,
+            tx_hash: format!("{:?}", tx.transaction_hash),
+            block_number: tx.block_number.unwrap_or_default(),
+            created_at: std::time::SystemTime::now(),
+            responded_at: None,
+        })
+        .await?;
+
     Ok(())
 }
 
 #[allow(unused)]
-/// Start creating tasks at every 15 seconds
+/// Number of worker tasks draining the submission queue.
+const SUBMIT_WORKERS: usize = 4;
+
+/// Build a [`TaskWatcher`] for the configured contract and spawn it in the
+/// background so latency reporting runs alongside task submission.
+async fn spawn_task_watcher(config: &TaskConfig, store: Arc<dyn TaskStore>) -> Result<()> {
+    let data = std::fs::read_to_string(&config.deployment_path)?;
+    let parsed: trappistData = serde_json::from_str(&data)?;
+    let contract_address: Address = parsed.addresses.trappist_service_manager.parse()?;
+
+    let signer = config.key_source.load()?;
+    let key_hex = hex::encode(signer.to_bytes());
+    let provider = get_signer(&key_hex, &config.rpc_url);
+
+    let watcher = TaskWatcher::new(provider, contract_address);
+    tokio::spawn(async move {
+        if let Err(err) = watcher.watch_latencies(store).await {
+            get_logger().error(&format!("task watcher stopped: {err}"), &"task_watcher");
+        }
+    });
+    Ok(())
+}
+
+/// Start creating tasks at every 15 seconds.
+///
+/// Each tick enqueues a [`SubmitTaskJob`] rather than submitting inline, so a
+/// flaky RPC endpoint triggers retries and the circuit breaker instead of
+/// silently losing tasks.
 async fn start_creating_tasks() {
     let mut interval = time::interval(Duration::from_secs(15));
     init_logger(LogLevel::Info);
+
+    // A DSN in `DATABASE_URL` selects Postgres; otherwise fall back to the
+    // in-memory store so the tool still runs without a database.
+    let pg_config = env::var("DATABASE_URL").ok().map(|dsn| PgConfig {
+        dsn,
+        pool_size: SUBMIT_WORKERS as u32,
+    });
+    let store = build_store(pg_config)
+        .await
+        .expect("failed to initialise task store");
+
+    let config = Arc::new(TaskConfig::default());
+
+    // Observe the AVS responding: watch the contract's lifecycle logs and emit
+    // round-trip latency alongside the submissions we generate.
+    match spawn_task_watcher(&config, store.clone()).await {
+        Ok(()) => {}
+        Err(err) => get_logger().error(
+            &format!("failed to start task watcher: {err}"),
+            &"start_creating_tasks",
+        ),
+    }
+
+    let queue = JobQueue::spawn(SUBMIT_WORKERS, {
+        let config = config.clone();
+        move |name| {
+            let store = store.clone();
+            let config = config.clone();
+            async move { create_new_task(&name, &store, &config).await }
+        }
+    });
+
     loop {
         interval.tick().await;
         let random_name = generate_random_name();
@@ -69,7 +201,11 @@ async fn start_creating_tasks() {
             &format!("Creating new task with name: {} ", random_name),
             &"start_creating_tasks",
         );
-        let _ = create_new_task(&random_name).await;
+        queue.enqueue(SubmitTaskJob::new(random_name)).await;
+        get_logger().info(
+            &format!("circuit breaker state: {:?}", queue.breaker_state().await),
+            &"start_creating_tasks",
+        );
     }
 }
 
@@ -0,0 +1,220 @@
+#![allow(missing_docs)]
+use async_trait::async_trait;
+use bb8::Pool;
+use bb8_postgres::PostgresConnectionManager;
+use eyre::Result;
+use std::sync::{Arc, Mutex};
+use tokio_postgres::NoTls;
+
+/// A task as recorded by the generator, enriched once a response is observed.
+#[derive(Debug, Clone)]
+pub struct TaskRecord {
+    pub name: String,
+    pub task_index: u32,
+    pub tx_hash: String,
+    pub block_number: u64,
+    pub created_at: std::time::SystemTime,
+    pub responded_at: Option<std::time::SystemTime>,
+}
+
+/// Durable history of tasks produced by `create_new_task`.
+///
+/// Implementations record each created task, fill in the responded-at time once
+/// the watcher sees a response, and expose recent rows for latency analysis.
+#[async_trait]
+pub trait TaskStore: Send + Sync {
+    /// Persist a freshly submitted task.
+    async fn record_created(&self, record: TaskRecord) -> Result<()>;
+    /// Mark the task with `task_index` as responded-to at `responded_at`.
+    async fn record_responded(
+        &self,
+        task_index: u32,
+        responded_at: std::time::SystemTime,
+    ) -> Result<()>;
+    /// Return up to `limit` most-recently-created tasks.
+    async fn recent(&self, limit: i64) -> Result<Vec<TaskRecord>>;
+}
+
+/// Connection settings for the Postgres-backed store.
+#[derive(Debug, Clone)]
+pub struct PgConfig {
+    /// libpq-style connection string.
+    pub dsn: String,
+    /// Maximum number of pooled connections shared across workers.
+    pub pool_size: u32,
+}
+
+/// An in-memory store used when no database is configured, so the tool still
+/// runs. Records live only as long as the process.
+#[derive(Default)]
+pub struct InMemoryTaskStore {
+    tasks: Mutex<Vec<TaskRecord>>,
+}
+
+#[async_trait]
+impl TaskStore for InMemoryTaskStore {
+    async fn record_created(&self, record: TaskRecord) -> Result<()> {
+        self.tasks.lock().unwrap().push(record);
+        Ok(())
+    }
+
+    async fn record_responded(
+        &self,
+        task_index: u32,
+        responded_at: std::time::SystemTime,
+    ) -> Result<()> {
+        if let Some(task) = self
+            .tasks
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .rev()
+            .find(|t| t.task_index == task_index)
+        {
+            task.responded_at = Some(responded_at);
+        }
+        Ok(())
+    }
+
+    async fn recent(&self, limit: i64) -> Result<Vec<TaskRecord>> {
+        let tasks = self.tasks.lock().unwrap();
+        Ok(tasks.iter().rev().take(limit.max(0) as usize).cloned().collect())
+    }
+}
+
+/// A Postgres-backed store using a shared `bb8` connection pool so concurrent
+/// workers reuse connections instead of opening one per insert.
+pub struct PostgresTaskStore {
+    pool: Pool<PostgresConnectionManager<NoTls>>,
+}
+
+impl PostgresTaskStore {
+    /// Connect to Postgres, build the pool and ensure the `tasks` table exists.
+    pub async fn connect(config: &PgConfig) -> Result<Self> {
+        let manager = PostgresConnectionManager::new_from_stringlike(&config.dsn, NoTls)?;
+        let pool = Pool::builder().max_size(config.pool_size).build(manager).await?;
+
+        pool.get()
+            .await?
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS tasks (
+                     task_index   BIGINT PRIMARY KEY,
+                     name         TEXT        NOT NULL,
+                     tx_hash      TEXT        NOT NULL,
+                     block_number BIGINT      NOT NULL,
+                     created_at   TIMESTAMPTZ NOT NULL,
+                     responded_at TIMESTAMPTZ
+                 )",
+            )
+            .await?;
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl TaskStore for PostgresTaskStore {
+    async fn record_created(&self, record: TaskRecord) -> Result<()> {
+        let conn = self.pool.get().await?;
+        conn.execute(
+            "INSERT INTO tasks (task_index, name, tx_hash, block_number, created_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (task_index) DO NOTHING",
+            &[
+                &(record.task_index as i64),
+                &record.name,
+                &record.tx_hash,
+                &(record.block_number as i64),
+                &record.created_at,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn record_responded(
+        &self,
+        task_index: u32,
+        responded_at: std::time::SystemTime,
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
+        conn.execute(
+            "UPDATE tasks SET responded_at = $2 WHERE task_index = $1",
+            &[&(task_index as i64), &responded_at],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn recent(&self, limit: i64) -> Result<Vec<TaskRecord>> {
+        let conn = self.pool.get().await?;
+        let rows = conn
+            .query(
+                "SELECT task_index, name, tx_hash, block_number, created_at, responded_at
+                 FROM tasks ORDER BY created_at DESC LIMIT $1",
+                &[&limit],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TaskRecord {
+                task_index: row.get::<_, i64>(0) as u32,
+                name: row.get(1),
+                tx_hash: row.get(2),
+                block_number: row.get::<_, i64>(3) as u64,
+                created_at: row.get(4),
+                responded_at: row.get(5),
+            })
+            .collect())
+    }
+}
+
+/// Build the configured store: Postgres when a DSN is present, otherwise the
+/// in-memory store so the tool runs without a database.
+pub async fn build_store(config: Option<PgConfig>) -> Result<Arc<dyn TaskStore>> {
+    match config {
+        Some(config) => Ok(Arc::new(PostgresTaskStore::connect(&config).await?)),
+        None => Ok(Arc::new(InMemoryTaskStore::default())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn record(index: u32) -> TaskRecord {
+        TaskRecord {
+            name: format!("task-{index}"),
+            task_index: index,
+            tx_hash: format!("0x{index:064x}"),
+            block_number: index as u64,
+            created_at: SystemTime::now(),
+            responded_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn recent_returns_newest_first_and_limits() {
+        let store = InMemoryTaskStore::default();
+        for i in 0..3 {
+            store.record_created(record(i)).await.unwrap();
+        }
+
+        let recent = store.recent(2).await.unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].task_index, 2);
+        assert_eq!(recent[1].task_index, 1);
+    }
+
+    #[tokio::test]
+    async fn record_responded_fills_timestamp() {
+        let store = InMemoryTaskStore::default();
+        store.record_created(record(7)).await.unwrap();
+        store.record_responded(7, SystemTime::now()).await.unwrap();
+
+        let recent = store.recent(1).await.unwrap();
+        assert!(recent[0].responded_at.is_some());
+    }
+}
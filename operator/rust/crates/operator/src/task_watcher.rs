@@ -0,0 +1,194 @@
+#![allow(missing_docs)]
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+    rpc::types::{Filter, Log},
+    sol_types::SolEvent,
+};
+use crate::task_store::TaskStore;
+use eigen_logging::get_logger;
+use eyre::Result;
+use futures::{stream, Stream};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use trappist_utils::trappistservicemanager::trappistServiceManager::{
+    NewTaskCreated, TaskResponded,
+};
+
+/// How often the watcher polls `eth_getFilterChanges` for new logs.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A decoded lifecycle event correlated by task index.
+#[derive(Debug, Clone)]
+pub enum TaskEvent {
+    /// A `createNewTask` call was mined; carries the on-chain task index.
+    Created { index: u32 },
+    /// An operator responded to the task with the given index.
+    Responded { index: u32 },
+}
+
+/// Subscribes to `NewTaskCreated` / `TaskResponded` logs on the service manager
+/// and reports the round-trip latency between a task being created and the AVS
+/// responding to it.
+///
+/// The watcher installs a single log filter via `eth_newFilter` scoped to the
+/// contract address and the two event topics, then polls `eth_getFilterChanges`
+/// on a fixed tick. If the node forgets the filter (e.g. after a restart) the
+/// poll loop re-installs it transparently.
+pub struct TaskWatcher<P> {
+    provider: P,
+    contract_address: Address,
+}
+
+impl<P> TaskWatcher<P>
+where
+    P: Provider + Clone + 'static,
+{
+    /// Create a watcher for the service manager deployed at `contract_address`.
+    pub fn new(provider: P, contract_address: Address) -> Self {
+        Self {
+            provider,
+            contract_address,
+        }
+    }
+
+    /// The filter matching both lifecycle events on our contract.
+    fn filter(&self) -> Filter {
+        Filter::new()
+            .address(self.contract_address)
+            .event_signature(vec![
+                NewTaskCreated::SIGNATURE_HASH,
+                TaskResponded::SIGNATURE_HASH,
+            ])
+    }
+
+    /// Install the filter, returning its server-side id.
+    async fn install_filter(&self) -> Result<alloy::primitives::U256> {
+        Ok(self.provider.new_filter(&self.filter()).await?)
+    }
+
+    /// Open a stream of decoded lifecycle events.
+    ///
+    /// Re-installs the filter on an `filter not found` RPC error so a node that
+    /// drops the subscription does not silently stall the stream.
+    pub async fn subscribe(self) -> Result<impl Stream<Item = TaskEvent>> {
+        let filter_id = self.install_filter().await?;
+        let state = (self, filter_id, Vec::<TaskEvent>::new());
+
+        Ok(stream::unfold(state, |(watcher, mut filter_id, mut pending)| async move {
+            loop {
+                if let Some(event) = pending.pop() {
+                    return Some((event, (watcher, filter_id, pending)));
+                }
+
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                match watcher.provider.get_filter_changes::<Log>(filter_id).await {
+                    Ok(logs) => {
+                        pending = logs.iter().filter_map(decode).rev().collect();
+                    }
+                    Err(err) if is_filter_not_found(&err) => {
+                        get_logger().warn(
+                            "filter dropped by node, re-installing",
+                            "task_watcher",
+                        );
+                        match watcher.install_filter().await {
+                            Ok(id) => filter_id = id,
+                            Err(reinstall) => {
+                                get_logger().error(
+                                    &format!("failed to re-install filter: {reinstall}"),
+                                    "task_watcher",
+                                );
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        get_logger()
+                            .error(&format!("eth_getFilterChanges failed: {err}"), "task_watcher");
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Drive the subscription, tracking in-flight tasks and logging the
+    /// round-trip latency once each one is answered. The responded-at time is
+    /// persisted through `store` so the history table is completed.
+    ///
+    /// Responses for task indices created before the watcher started (absent
+    /// from the map) are ignored.
+    pub async fn watch_latencies(self, store: Arc<dyn TaskStore>) -> Result<()> {
+        use futures::StreamExt;
+
+        let mut in_flight: HashMap<u32, Instant> = HashMap::new();
+        let mut events = Box::pin(self.subscribe().await?);
+
+        while let Some(event) = events.next().await {
+            match event {
+                TaskEvent::Created { index } => {
+                    in_flight.insert(index, Instant::now());
+                }
+                TaskEvent::Responded { index } => match in_flight.remove(&index) {
+                    Some(started) => {
+                        get_logger().info(
+                            &format!(
+                                "task {index} responded in {} ms",
+                                started.elapsed().as_millis()
+                            ),
+                            "task_watcher",
+                        );
+                        if let Err(err) = store.record_responded(index, SystemTime::now()).await {
+                            get_logger().error(
+                                &format!("failed to persist response for task {index}: {err}"),
+                                "task_watcher",
+                            );
+                        }
+                    }
+                    None => get_logger().debug(
+                        &format!("response for unknown task {index}, ignoring"),
+                        "task_watcher",
+                    ),
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decode a raw log into a [`TaskEvent`], or `None` if it is neither of the
+/// events we care about (keeps us tolerant of unrelated topics).
+fn decode(log: &Log) -> Option<TaskEvent> {
+    if let Ok(ev) = log.log_decode::<NewTaskCreated>() {
+        return Some(TaskEvent::Created {
+            index: ev.inner.taskIndex,
+        });
+    }
+    if let Ok(ev) = log.log_decode::<TaskResponded>() {
+        return Some(TaskEvent::Responded {
+            index: ev.inner.taskResponse.referenceTaskIndex,
+        });
+    }
+    None
+}
+
+/// Whether an RPC error is the node reporting that our filter id is gone.
+fn is_filter_not_found(err: &alloy::transports::RpcError<alloy::transports::TransportErrorKind>) -> bool {
+    err.to_string().to_lowercase().contains("filter not found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_ignores_unrelated_logs() {
+        // A log carrying neither lifecycle topic must decode to `None` so the
+        // watcher stays tolerant of unrelated events on the same contract.
+        let log = Log::default();
+        assert!(decode(&log).is_none());
+    }
+}